@@ -0,0 +1,31 @@
+use std::sync::Arc;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum_extra::headers::{authorization::Bearer, Authorization};
+use axum_extra::TypedHeader;
+use crate::auth::jwt::verify_token;
+use crate::db::{get_user_by_id, User};
+use crate::error::Error;
+use crate::state::AppState;
+
+pub struct AuthUser(pub User);
+
+#[axum::async_trait]
+impl FromRequestParts<Arc<AppState>> for AuthUser {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let TypedHeader(Authorization(bearer)) = TypedHeader::<Authorization<Bearer>>::from_request_parts(parts, state)
+            .await
+            .map_err(|_| Error::Unauthorized)?;
+
+        let claims = verify_token(bearer.token(), &state.config.jwt_secret).map_err(|_| Error::Unauthorized)?;
+
+        let user = get_user_by_id(&state.db, &claims.sub)
+            .await
+            .map_err(|_| Error::Internal)?
+            .ok_or(Error::Unauthorized)?;
+
+        Ok(AuthUser(user))
+    }
+}
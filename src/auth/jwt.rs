@@ -0,0 +1,22 @@
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: Uuid,
+    pub exp: usize,
+}
+
+pub fn issue_token(user_id: &Uuid, secret: &str, expires_in_seconds: i64) -> Result<String, jsonwebtoken::errors::Error> {
+    let exp = (Utc::now() + Duration::seconds(expires_in_seconds)).timestamp() as usize;
+    let claims = Claims { sub: *user_id, exp };
+
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes()))
+}
+
+pub fn verify_token(token: &str, secret: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    decode::<Claims>(token, &DecodingKey::from_secret(secret.as_bytes()), &Validation::default())
+        .map(|data| data.claims)
+}
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Uuid;
+use crate::auth::jwt::issue_token;
+use crate::db::{create_user, get_user_by_name};
+use crate::error::{Error, Result};
+use crate::state::AppState;
+
+pub fn get_router(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/auth/register", post(register_handler))
+        .route("/auth/login", post(login_handler))
+        .with_state(app_state)
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct RegisterRequest {
+    name: String,
+    email: String,
+    password: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct RegisterResponse {
+    id: Uuid,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct LoginRequest {
+    name: String,
+    password: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct LoginResponse {
+    token: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/register",
+    tag = "auth",
+    request_body = RegisterRequest,
+    responses(
+        (status = 200, description = "User registered", body = RegisterResponse),
+        (status = 400, description = "Name or email already taken"),
+    ),
+)]
+pub(crate) async fn register_handler(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<RegisterRequest>,
+) -> Result<Json<RegisterResponse>> {
+    let salt = SaltString::generate(&mut OsRng);
+    let password_hash = Argon2::default()
+        .hash_password(payload.password.as_bytes(), &salt)
+        .map_err(|_| Error::Internal)?
+        .to_string();
+
+    let user = create_user(&state.db, &payload.name, &payload.email, password_hash.as_bytes())
+        .await
+        .map_err(|err| match &err {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                Error::BadRequest("name or email already taken".into())
+            }
+            _ => Error::Database(err),
+        })?;
+
+    Ok(Json(RegisterResponse { id: user.id }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Bearer JWT issued", body = LoginResponse),
+        (status = 401, description = "Invalid credentials"),
+    ),
+)]
+pub(crate) async fn login_handler(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Json<LoginResponse>> {
+    let user = get_user_by_name(&state.db, &payload.name)
+        .await?
+        .ok_or(Error::Unauthorized)?;
+
+    let stored_hash = std::str::from_utf8(&user.password_hash).map_err(|_| Error::Unauthorized)?;
+    let parsed_hash = PasswordHash::new(stored_hash).map_err(|_| Error::Unauthorized)?;
+
+    Argon2::default()
+        .verify_password(payload.password.as_bytes(), &parsed_hash)
+        .map_err(|_| Error::Unauthorized)?;
+
+    let token = issue_token(&user.id, &state.config.jwt_secret, state.config.jwt_expires_in)
+        .map_err(|_| Error::Internal)?;
+
+    Ok(Json(LoginResponse { token }))
+}
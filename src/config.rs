@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct Config {
+    pub database_url: String,
+    pub db_min_connections: u32,
+    pub db_max_connections: u32,
+    pub db_acquire_timeout: Duration,
+    pub jwt_secret: String,
+    pub jwt_expires_in: i64,
+    pub bind_address: String,
+    pub tg_max_send_attempts: u32,
+}
+
+impl Config {
+    pub fn from_env() -> Self {
+        Self {
+            database_url: must_env("DATABASE_URL"),
+            db_min_connections: env_or("DB_MIN_CONNECTIONS", 1),
+            db_max_connections: env_or("DB_MAX_CONNECTIONS", 10),
+            db_acquire_timeout: Duration::from_secs(env_or("DB_ACQUIRE_TIMEOUT_SECS", 5)),
+            jwt_secret: must_env("JWT_SECRET"),
+            jwt_expires_in: env_or("JWT_EXPIRES_IN", 3600),
+            bind_address: std::env::var("BIND_ADDRESS").unwrap_or_else(|_| "0.0.0.0:3000".to_string()),
+            tg_max_send_attempts: env_or("TG_MAX_SEND_ATTEMPTS", 5),
+        }
+    }
+}
+
+fn must_env(key: &str) -> String {
+    std::env::var(key).unwrap_or_else(|_| panic!("missing required env var {key}"))
+}
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    std::env::var(key).ok().and_then(|value| value.parse().ok()).unwrap_or(default)
+}
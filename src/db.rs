@@ -1,9 +1,20 @@
 use serde::Serialize;
+use sqlx::postgres::PgPoolOptions;
 use sqlx::PgPool;
 use sqlx::types::{Uuid, chrono::NaiveDateTime};
+use crate::config::Config;
+
+pub async fn get_db_connection(config: &Config) -> Result<PgPool, sqlx::Error> {
+    let pool = PgPoolOptions::new()
+        .min_connections(config.db_min_connections)
+        .max_connections(config.db_max_connections)
+        .acquire_timeout(config.db_acquire_timeout)
+        .connect(&config.database_url)
+        .await?;
+
+    sqlx::migrate!().run(&pool).await?;
 
-pub async fn get_db_connection(db_url: &str) -> Result<PgPool, sqlx::Error> {
-    PgPool::connect(db_url).await
+    Ok(pool)
 }
 
 
@@ -60,6 +71,13 @@ pub async fn increment_short_link_clicks(db: &PgPool, short_key: &str) -> Result
     Ok(())
 }
 
+pub async fn next_short_link_counter(db: &PgPool) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!("SELECT nextval('short_link_id_seq')")
+        .fetch_one(db)
+        .await
+        .map(|counter| counter.unwrap_or_default())
+}
+
 
 pub struct LandingPage {
     pub id: i32,
@@ -93,14 +111,23 @@ pub async fn get_landing_page(db: &PgPool, path: &str) -> Result<LandingPage, sq
 }
 
 
-#[derive(Serialize, sqlx::FromRow)]
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct Topic {
     pub id: Uuid,
     pub name: String,
-    pub tg_api: Option<serde_json::Value>
+    pub tg_api: Option<serde_json::Value>,
+    pub owner_id: Uuid,
 }
 
 #[derive(Serialize, sqlx::FromRow)]
+pub struct User {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub password_hash: Vec<u8>,
+}
+
+#[derive(Clone, Serialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct Message {
     pub id: i32,
     pub created_at: NaiveDateTime,
@@ -109,12 +136,13 @@ pub struct Message {
     pub topic_id: Uuid,
 }
 
-pub async fn create_topic(db: &PgPool, name: &str, tg_api: Option<serde_json::Value>) -> Result<Topic, sqlx::Error> {
+pub async fn create_topic(db: &PgPool, name: &str, tg_api: Option<serde_json::Value>, owner_id: &Uuid) -> Result<Topic, sqlx::Error> {
     sqlx::query_as!(
         Topic,
-        "INSERT INTO topic (name, tg_api) VALUES ($1, $2::jsonb) RETURNING *",
+        "INSERT INTO topic (name, tg_api, owner_id) VALUES ($1, $2::jsonb, $3) RETURNING *",
         name,
         tg_api,
+        owner_id,
     )
     .fetch_one(db)
     .await
@@ -130,15 +158,66 @@ pub async fn get_topic(db: &PgPool, topic_id: &Uuid) -> Result<Option<Topic>, sq
     .await
 }
 
-pub async fn create_message(db: &PgPool, contacts: &serde_json::Value, text: &str, topic_id: &Uuid) -> Result<Message, sqlx::Error> {
+pub async fn create_user(db: &PgPool, name: &str, email: &str, password_hash: &[u8]) -> Result<User, sqlx::Error> {
+    sqlx::query_as!(
+        User,
+        "INSERT INTO users (name, email, password_hash) VALUES ($1, $2, $3) RETURNING *",
+        name,
+        email,
+        password_hash,
+    )
+    .fetch_one(db)
+    .await
+}
+
+pub async fn get_user_by_name(db: &PgPool, name: &str) -> Result<Option<User>, sqlx::Error> {
+    sqlx::query_as!(
+        User,
+        "SELECT * FROM users WHERE name = $1",
+        name
+    )
+    .fetch_optional(db)
+    .await
+}
+
+pub async fn get_user_by_id(db: &PgPool, user_id: &Uuid) -> Result<Option<User>, sqlx::Error> {
     sqlx::query_as!(
+        User,
+        "SELECT * FROM users WHERE id = $1",
+        user_id
+    )
+    .fetch_optional(db)
+    .await
+}
+
+pub async fn create_message(db: &PgPool, contacts: &serde_json::Value, text: &str, topic_id: &Uuid) -> Result<Message, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let message = sqlx::query_as!(
         Message,
         "INSERT INTO message (contacts, text, topic_id) VALUES ($1::jsonb, $2, $3) RETURNING *",
         contacts,
         text,
         topic_id
     )
-    .fetch_one(db)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let payload = serde_json::json!({ "message_id": message.id, "topic_id": message.topic_id }).to_string();
+    sqlx::query!("SELECT pg_notify('new_message', $1)", payload)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(message)
+}
+
+pub async fn get_message(db: &PgPool, message_id: i32) -> Result<Option<Message>, sqlx::Error> {
+    sqlx::query_as!(
+        Message, "SELECT * FROM message WHERE id = $1", message_id
+    )
+    .fetch_optional(db)
     .await
 }
 
@@ -149,3 +228,14 @@ pub async fn get_messages(db: &PgPool, topic_id: &Uuid) -> Result<Vec<Message>,
     .fetch_all(db)
     .await
 }
+
+pub async fn get_recent_messages(db: &PgPool, topic_id: &Uuid, limit: i64) -> Result<Vec<Message>, sqlx::Error> {
+    sqlx::query_as!(
+        Message,
+        "SELECT * FROM message WHERE topic_id = $1 ORDER BY created_at DESC LIMIT $2",
+        topic_id,
+        limit
+    )
+    .fetch_all(db)
+    .await
+}
@@ -0,0 +1,55 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::json;
+use thiserror::Error;
+use tracing::error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("not found")]
+    NotFound,
+
+    #[error("unauthorized")]
+    Unauthorized,
+
+    #[error("bad request: {0}")]
+    BadRequest(String),
+
+    #[error("telegram check failed")]
+    TelegramCheckFailed,
+
+    #[error("internal error")]
+    Internal,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            Error::Database(_) | Error::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::NotFound => StatusCode::NOT_FOUND,
+            Error::Unauthorized => StatusCode::UNAUTHORIZED,
+            Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Error::TelegramCheckFailed => StatusCode::BAD_REQUEST,
+        };
+
+        let message = if status == StatusCode::INTERNAL_SERVER_ERROR {
+            error!("{:?}", self);
+            "internal server error".to_string()
+        } else {
+            self.to_string()
+        };
+
+        let body = Json(json!({
+            "status": status.as_u16(),
+            "message": message,
+        }));
+
+        (status, body).into_response()
+    }
+}
@@ -1,19 +1,19 @@
-use std::fmt::Debug;
 use std::sync::Arc;
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use axum::{Json, Router};
 use axum::routing::{get, post};
-use axum_extra::{
-    headers::{authorization::Bearer, Authorization},
-    TypedHeader,
-};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use sqlx::types::Uuid;
 use tracing::{error, info, warn};
+use crate::auth::extractor::AuthUser;
+use crate::auth;
 use crate::db::{create_topic, get_topic, create_message, get_messages, Message};
+use crate::error::{Error, Result};
+use crate::message::stream;
+use crate::short_link;
 use crate::state::AppState;
 use crate::utils;
 
@@ -23,112 +23,144 @@ pub async fn get_router(app_state: Arc<AppState>) -> Router {
         .route("/topics", post(create_topic_handler))
         .route("/topics/:topic_id/messages", post(create_message_handler))
         .route("/topics/:topic_id/messages", get(get_messages_handler))
-        .with_state(app_state)
+        .with_state(app_state.clone())
+        .merge(short_link::router::get_router(app_state.clone()))
+        .merge(auth::router::get_router(app_state.clone()))
+        .merge(stream::get_router(app_state))
+        .merge(crate::openapi::get_router())
 }
 
-#[derive(Deserialize)]
-struct CreateTopicRequest {
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct CreateTopicRequest {
     name: String,
     tg_api: Option<TgApi>,
 }
 
-#[derive(Serialize)]
-struct CreateTopicResponse {
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct CreateTopicResponse {
     id: Uuid,
 }
 
-#[derive(Deserialize)]
-struct CreateMessageRequest {
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct CreateMessageRequest {
     contacts: Value,
     text: String,
 }
 
-async fn create_topic_handler(
+#[utoipa::path(
+    post,
+    path = "/topics",
+    tag = "topics",
+    request_body = CreateTopicRequest,
+    responses(
+        (status = 200, description = "Topic created", body = CreateTopicResponse),
+        (status = 400, description = "tg_api check failed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal error"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub(crate) async fn create_topic_handler(
     State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
     Json(payload): Json<CreateTopicRequest>,
-) -> Result<Json<CreateTopicResponse>, StatusCode> {
+) -> Result<Json<CreateTopicResponse>> {
     let tg_api = match payload.tg_api {
         None => None,
         Some(tg_api) => match tg_api.check().await {
             Ok(v) if v => serde_json::to_value(tg_api.clone()).map_or(None, |v| Some(v)),
-            _ => return Err(StatusCode::BAD_REQUEST),
+            _ => return Err(Error::TelegramCheckFailed),
         }
     };
 
-    let topic = create_topic(&state.db, &payload.name, tg_api)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let topic = create_topic(&state.db, &payload.name, tg_api, &user.id).await?;
 
     Ok(Json(CreateTopicResponse { id: topic.id }))
 }
 
-async fn create_message_handler(
+#[utoipa::path(
+    post,
+    path = "/topics/{topic_id}/messages",
+    tag = "topics",
+    params(("topic_id" = Uuid, Path, description = "Topic id")),
+    request_body = CreateMessageRequest,
+    responses(
+        (status = 201, description = "Message created"),
+        (status = 404, description = "Topic not found"),
+    ),
+)]
+pub(crate) async fn create_message_handler(
     Path(topic_id): Path<Uuid>,
     State(state): State<Arc<AppState>>,
     Json(payload): Json<CreateMessageRequest>,
-) -> Result<StatusCode, StatusCode> {
-    match get_topic(&state.db, &topic_id)
-        .await
-        .map_err(|_| StatusCode::NOT_FOUND)? {
-        None => Err(StatusCode::NOT_FOUND),
-        Some(topic) => {
-            let message = create_message(&state.db, &payload.contacts, &payload.text, &topic_id)
-                .await
-                .map_err(|err| log_and_raise("Failed create_message", err))?;
-
-            if let Some(tg_api) = topic.tg_api {
-                match TgApi::try_from(tg_api) {
-                    Err(_) => warn!("Failed parse TgApi for Topic(id={})", &topic_id),
-                    Ok(tg_api) => tg_api.send(&topic.name, message).await
-                };
-            }
+) -> Result<StatusCode> {
+    let topic = get_topic(&state.db, &topic_id)
+        .await?
+        .ok_or(Error::NotFound)?;
 
-            Ok(StatusCode::CREATED)
-        }
+    let message = create_message(&state.db, &payload.contacts, &payload.text, &topic_id).await?;
+
+    if let Some(tg_api) = topic.tg_api {
+        match TgApi::try_from(tg_api) {
+            Err(_) => warn!("Failed parse TgApi for Topic(id={})", &topic_id),
+            Ok(tg_api) => tg_api.send(&topic.name, message, state.config.tg_max_send_attempts).await
+        };
     }
-}
 
-fn log_and_raise(pre_message: &str, err: impl Debug) -> StatusCode {
-    error!("{}: {:?}", pre_message, err);
-    StatusCode::INTERNAL_SERVER_ERROR
+    Ok(StatusCode::CREATED)
 }
 
-async fn get_messages_handler(
+#[utoipa::path(
+    get,
+    path = "/topics/{topic_id}/messages",
+    tag = "topics",
+    params(("topic_id" = Uuid, Path, description = "Topic id")),
+    responses(
+        (status = 200, description = "Messages for topic", body = [Message]),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Topic not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub(crate) async fn get_messages_handler(
     Path(topic_id): Path<Uuid>,
-    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+    AuthUser(user): AuthUser,
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<Message>>, StatusCode> {
-    let token = std::env::var("CONTACT_TOKEN").map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    if bearer.token() != token {
-        return Err(StatusCode::UNAUTHORIZED);
+) -> Result<Json<Vec<Message>>> {
+    let topic = get_topic(&state.db, &topic_id)
+        .await?
+        .ok_or(Error::NotFound)?;
+
+    if topic.owner_id != user.id {
+        return Err(Error::Unauthorized);
     }
 
-    let messages = get_messages(&state.db, &topic_id)
-        .await
-        .map_err(|_| StatusCode::NOT_FOUND)?;
+    let messages = get_messages(&state.db, &topic_id).await?;
 
     Ok(Json(messages))
 }
 
 const TELEGRAM_API_URL: &str = "https://api.telegram.org/bot";
+const MAX_BACKOFF_SECS: u64 = 30;
 
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize, utoipa::ToSchema)]
 struct TgApi {
     api_key: String,
     chat_id: String,
+    parse_mode: Option<String>,
 }
 
 impl TryFrom<Value> for TgApi {
     type Error = ();
 
-    fn try_from(value: Value) -> Result<Self, Self::Error> {
+    fn try_from(value: Value) -> std::result::Result<Self, Self::Error> {
         serde_json::from_value::<TgApi>(value)
             .map_err(|_| ())
     }
 }
 
 impl TgApi {
-    async fn check(&self) -> Result<bool, String> {
+    async fn check(&self) -> std::result::Result<bool, String> {
         Client::new()
             .get(format!("{}{}/getMe", TELEGRAM_API_URL, self.api_key))
             .send()
@@ -137,40 +169,105 @@ impl TgApi {
             .map_err(|err| format!("Failed check api_key: {:?}", err))
     }
 
-    async fn send(&self, topic_name: &str, message: Message) {
+    async fn send(&self, topic_name: &str, message: Message, max_attempts: u32) {
         let api_key = self.api_key.clone();
         let chat_id = self.chat_id.clone();
+        let parse_mode = self.parse_mode.clone();
         let message_id = message.id;
         let contacts = serde_json::to_string(&message.contacts).unwrap_or_default();
-        let message = format!(
-            "Topic: {}\nText: {}\nContacts: {}",
-            topic_name, message.text, contacts
-        );
+
+        let text = match parse_mode.as_deref() {
+            Some("MarkdownV2") => format!(
+                "*Topic:* {}\n*Text:* {}\n*Contacts:* {}",
+                escape_markdown_v2(topic_name), escape_markdown_v2(&message.text), escape_markdown_v2(&contacts)
+            ),
+            Some("HTML") => format!(
+                "<b>Topic:</b> {}\n<b>Text:</b> {}\n<b>Contacts:</b> {}",
+                escape_html(topic_name), escape_html(&message.text), escape_html(&contacts)
+            ),
+            _ => format!("Topic: {}\nText: {}\nContacts: {}", topic_name, message.text, contacts),
+        };
 
         tokio::spawn(async move {
-            match Client::new()
-                .post(format!("{}{}/sendMessage", TELEGRAM_API_URL, api_key))
-                .json(&json!({"chat_id": chat_id, "text": message}))
-                .send()
-                .await {
-                Err(err) => error!(
-                    "Message(id={}) sending failed. Failed send request: {:?}",
-                    message_id, err
-                ),
-                Ok(response) => match response.status().is_success() {
-                    true => info!("Message(id={}) sent successfully", message_id),
-                    false => match response.text().await {
-                        Err(err) => error!(
-                            "Message(id={}) sending failed. Failed get response: {:?}",
-                            message_id, err
-                        ),
-                        Ok(text) => warn!(
-                            "Message(id={}) sending failed. Response text={:?}",
-                            message_id, text
-                        )
-                    }
+            send_with_retries(&api_key, &chat_id, &text, parse_mode.as_deref(), message_id, max_attempts).await;
+        });
+    }
+}
+
+async fn send_with_retries(api_key: &str, chat_id: &str, text: &str, parse_mode: Option<&str>, message_id: i32, max_attempts: u32) {
+    let client = Client::new();
+    let mut payload = json!({ "chat_id": chat_id, "text": text });
+    if let Some(parse_mode) = parse_mode {
+        payload["parse_mode"] = json!(parse_mode);
+    }
+
+    for attempt in 1..=max_attempts {
+        match client
+            .post(format!("{}{}/sendMessage", TELEGRAM_API_URL, api_key))
+            .json(&payload)
+            .send()
+            .await
+        {
+            Err(err) => {
+                warn!("Message(id={}) send attempt {} failed: {:?}", message_id, attempt, err);
+                backoff(attempt).await;
+            }
+            Ok(response) if response.status().is_success() => {
+                info!("Message(id={}) sent successfully", message_id);
+                return;
+            }
+            Ok(response) => {
+                let status = response.status();
+                let retry_after = response
+                    .json::<TelegramErrorResponse>()
+                    .await
+                    .ok()
+                    .and_then(|body| body.parameters)
+                    .and_then(|parameters| parameters.retry_after);
+
+                warn!("Message(id={}) send attempt {} failed with status {}", message_id, attempt, status);
+
+                match retry_after {
+                    Some(retry_after_secs) => tokio::time::sleep(std::time::Duration::from_secs(retry_after_secs)).await,
+                    None => backoff(attempt).await,
                 }
             }
-        });
+        }
     }
+
+    error!("Message(id={}) sending failed after {} attempts", message_id, max_attempts);
+}
+
+async fn backoff(attempt: u32) {
+    let backoff_secs = 2u64.saturating_pow(attempt).min(MAX_BACKOFF_SECS);
+    tokio::time::sleep(std::time::Duration::from_secs(backoff_secs)).await;
+}
+
+#[derive(Deserialize)]
+struct TelegramErrorResponse {
+    parameters: Option<TelegramErrorParameters>,
+}
+
+#[derive(Deserialize)]
+struct TelegramErrorParameters {
+    retry_after: Option<u64>,
+}
+
+fn escape_markdown_v2(text: &str) -> String {
+    const SPECIAL_CHARS: &[char] = &[
+        '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!',
+    ];
+
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if SPECIAL_CHARS.contains(&ch) {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
 }
\ No newline at end of file
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use axum::extract::{Path, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::get;
+use axum::Router;
+use futures::Stream;
+use serde::Deserialize;
+use sqlx::postgres::PgListener;
+use sqlx::types::Uuid;
+use sqlx::PgPool;
+use tokio::sync::{broadcast, RwLock};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt as _;
+use tracing::{error, warn};
+use crate::auth::extractor::AuthUser;
+use crate::db::{get_message, get_recent_messages, get_topic, Message};
+use crate::error::{Error, Result};
+use crate::state::AppState;
+
+const NEW_MESSAGE_CHANNEL: &str = "new_message";
+const REPLAY_LIMIT: i64 = 20;
+const BROADCAST_CAPACITY: usize = 100;
+
+type TopicBroadcasters = RwLock<HashMap<Uuid, broadcast::Sender<Message>>>;
+
+pub fn get_router(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/topics/:topic_id/messages/stream", get(stream_handler))
+        .with_state(app_state)
+}
+
+#[derive(Deserialize)]
+struct NewMessageNotification {
+    message_id: i32,
+    topic_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/topics/{topic_id}/messages/stream",
+    tag = "topics",
+    params(("topic_id" = Uuid, Path, description = "Topic id")),
+    responses(
+        (status = 200, description = "Server-sent event stream of new messages"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Topic not found"),
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub(crate) async fn stream_handler(
+    Path(topic_id): Path<Uuid>,
+    AuthUser(user): AuthUser,
+    State(state): State<Arc<AppState>>,
+) -> Result<Sse<impl Stream<Item = std::result::Result<Event, Infallible>>>> {
+    let topic = get_topic(&state.db, &topic_id)
+        .await?
+        .ok_or(Error::NotFound)?;
+
+    if topic.owner_id != user.id {
+        return Err(Error::Unauthorized);
+    }
+
+    ensure_listener_started(state.db.clone());
+
+    // Subscribe before the replay fetch so messages committed in between do not
+    // fall into the gap between the two; they arrive in the replay, and the live
+    // stream skips any message id the replay already holds.
+    let receiver = sender_for(topic_id).await.subscribe();
+
+    let mut recent = get_recent_messages(&state.db, &topic_id, REPLAY_LIMIT).await?;
+    recent.reverse();
+
+    let seen_ids: std::collections::HashSet<i32> = recent.iter().map(|message| message.id).collect();
+
+    let replay = tokio_stream::iter(recent).map(to_event);
+    let live = BroadcastStream::new(receiver).filter_map(move |message| {
+        let message = message.ok()?;
+        if seen_ids.contains(&message.id) {
+            return None;
+        }
+        Some(to_event(message))
+    });
+
+    Ok(Sse::new(replay.chain(live)).keep_alive(KeepAlive::default()))
+}
+
+fn to_event(message: Message) -> std::result::Result<Event, Infallible> {
+    Ok(Event::default().json_data(message).unwrap_or_else(|_| Event::default()))
+}
+
+fn broadcasters() -> &'static TopicBroadcasters {
+    static BROADCASTERS: OnceLock<TopicBroadcasters> = OnceLock::new();
+    BROADCASTERS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+async fn sender_for(topic_id: Uuid) -> broadcast::Sender<Message> {
+    if let Some(sender) = broadcasters().read().await.get(&topic_id) {
+        return sender.clone();
+    }
+
+    let mut broadcasters = broadcasters().write().await;
+    broadcasters
+        .entry(topic_id)
+        .or_insert_with(|| broadcast::channel(BROADCAST_CAPACITY).0)
+        .clone()
+}
+
+fn ensure_listener_started(db: PgPool) {
+    static LISTENER_STARTED: OnceLock<()> = OnceLock::new();
+
+    if LISTENER_STARTED.set(()).is_ok() {
+        tokio::spawn(async move {
+            loop {
+                if let Err(err) = listen(&db).await {
+                    error!("message stream listener failed, reconnecting: {:?}", err);
+                }
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        });
+    }
+}
+
+async fn listen(db: &PgPool) -> Result<()> {
+    let mut listener = PgListener::connect_with(db).await?;
+    listener.listen(NEW_MESSAGE_CHANNEL).await?;
+
+    loop {
+        let notification = listener.recv().await?;
+
+        let payload: NewMessageNotification = match serde_json::from_str(notification.payload()) {
+            Ok(payload) => payload,
+            Err(err) => {
+                warn!("Failed parse new_message payload: {:?}", err);
+                continue;
+            }
+        };
+
+        let message = match get_message(db, payload.message_id).await {
+            Ok(Some(message)) => message,
+            Ok(None) => continue,
+            Err(err) => {
+                warn!("Failed fetch Message(id={}) for stream: {:?}", payload.message_id, err);
+                continue;
+            }
+        };
+
+        let _ = sender_for(payload.topic_id).await.send(message);
+    }
+}
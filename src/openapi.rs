@@ -0,0 +1,51 @@
+use axum::Router;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+use crate::auth::router::{LoginRequest, LoginResponse, RegisterRequest, RegisterResponse};
+use crate::db::{Message, Topic};
+use crate::message::router::{CreateMessageRequest, CreateTopicRequest, CreateTopicResponse};
+use crate::short_link::router::{CreateShortLinkRequest, CreateShortLinkResponse};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        crate::message::router::create_topic_handler,
+        crate::message::router::create_message_handler,
+        crate::message::router::get_messages_handler,
+        crate::message::stream::stream_handler,
+        crate::auth::router::register_handler,
+        crate::auth::router::login_handler,
+        crate::short_link::router::create_short_link_handler,
+        crate::short_link::router::redirect_handler,
+    ),
+    components(schemas(
+        CreateTopicRequest, CreateTopicResponse, CreateMessageRequest, Topic, Message,
+        RegisterRequest, RegisterResponse, LoginRequest, LoginResponse,
+        CreateShortLinkRequest, CreateShortLinkResponse,
+    )),
+    tags(
+        (name = "topics", description = "Topics and messages API"),
+        (name = "auth", description = "Registration, login and JWT issuance"),
+        (name = "short_link", description = "Short link creation and redirects"),
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "bearer_auth",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+            );
+        }
+    }
+}
+
+pub fn get_router() -> Router {
+    Router::new().merge(SwaggerUi::new("/docs").url("/api-doc/openapi.json", ApiDoc::openapi()))
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use sqids::Sqids;
+use crate::db::{check_key_exists, create_short_link, get_short_link, increment_short_link_clicks, next_short_link_counter};
+use crate::error::{Error, Result};
+use crate::state::AppState;
+
+const SHORT_KEY_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const SHORT_KEY_MIN_LENGTH: u8 = 6;
+
+pub fn get_router(app_state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/links", post(create_short_link_handler))
+        .route("/:short_key", get(redirect_handler))
+        .with_state(app_state)
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct CreateShortLinkRequest {
+    url: String,
+    token: String,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct CreateShortLinkResponse {
+    short_key: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/links",
+    tag = "short_link",
+    request_body = CreateShortLinkRequest,
+    responses(
+        (status = 200, description = "Short link created", body = CreateShortLinkResponse),
+    ),
+)]
+pub(crate) async fn create_short_link_handler(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreateShortLinkRequest>,
+) -> Result<Json<CreateShortLinkResponse>> {
+    let short_key = generate_unique_short_key(&state.db).await?;
+
+    let short_link = create_short_link(&state.db, &short_key, &payload.url, &payload.token).await?;
+
+    Ok(Json(CreateShortLinkResponse { short_key: short_link.short_key }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{short_key}",
+    tag = "short_link",
+    params(("short_key" = String, Path, description = "Short link key")),
+    responses(
+        (status = 302, description = "Redirect to the short link's target URL"),
+        (status = 404, description = "Short link not found"),
+    ),
+)]
+pub(crate) async fn redirect_handler(
+    Path(short_key): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Response> {
+    let short_link = get_short_link(&state.db, &short_key)
+        .await
+        .map_err(|err| match err {
+            sqlx::Error::RowNotFound => Error::NotFound,
+            err => Error::Database(err),
+        })?;
+
+    increment_short_link_clicks(&state.db, &short_key).await?;
+
+    Ok((StatusCode::FOUND, [(header::LOCATION, short_link.url)]).into_response())
+}
+
+fn sqids() -> Sqids {
+    Sqids::builder()
+        .alphabet(SHORT_KEY_ALPHABET.chars().collect())
+        .min_length(SHORT_KEY_MIN_LENGTH)
+        .build()
+        .expect("SHORT_KEY_ALPHABET must be a valid sqids alphabet")
+}
+
+async fn generate_unique_short_key(db: &PgPool) -> Result<String> {
+    let sqids = sqids();
+
+    loop {
+        let counter = next_short_link_counter(db).await?;
+        let short_key = sqids.encode(&[counter as u64]).map_err(|_| Error::Internal)?;
+
+        match check_key_exists(db, &short_key).await.map_err(|_| Error::Internal)? {
+            false => return Ok(short_key),
+            true => continue,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use sqlx::PgPool;
+use crate::config::Config;
+
+pub struct AppState {
+    pub db: PgPool,
+    pub config: Config,
+}